@@ -0,0 +1,229 @@
+use std::{
+  fmt::Debug,
+  future::Future,
+  pin::Pin,
+  sync::{mpsc::RecvTimeoutError, Arc},
+  time::Duration,
+};
+
+use oura::{
+  model::Event,
+  pipelining::{BootstrapResult, SinkProvider, StageReceiver},
+  utils::Utils,
+};
+use strum_macros::Display;
+use tokio::{runtime::Runtime, time::Instant};
+use tracing::{event, span, Instrument, Level};
+
+use super::{
+  error::ErrorPolicyProvider,
+  retry::{perform_with_retry, RetryPolicy},
+};
+
+/// A batching variant of [`super::callback::Callback`]. Instead of invoking
+/// the callback once per chain event, it accumulates events into a buffer and
+/// flushes the whole batch whenever either `max_items` is reached or the
+/// oldest buffered event has been waiting longer than `max_latency`, which is
+/// more efficient for indexing workloads that want to write many events in a
+/// single DB transaction.
+pub(crate) struct BatchingCallback<E> {
+  pub(crate) f: Arc<
+    dyn Fn(Vec<Event>) -> Pin<Box<dyn Future<Output = Result<(), E>> + Send + Sync>>
+      + Send
+      + Sync,
+  >,
+  pub(crate) retry_policy: RetryPolicy,
+  pub(crate) utils: Arc<Utils>,
+  pub(crate) max_items: usize,
+  pub(crate) max_latency: Duration,
+}
+
+impl<E: Debug + ErrorPolicyProvider + 'static> SinkProvider for BatchingCallback<E> {
+  fn bootstrap(&self, input: StageReceiver) -> BootstrapResult {
+    let span = span!(Level::INFO, "BatchingCallback::bootstrap");
+    let _enter = span.enter();
+
+    let retry_policy = self.retry_policy;
+    let utils = self.utils.clone();
+    let max_items = self.max_items;
+    let max_latency = self.max_latency;
+
+    let f = Arc::clone(&self.f);
+    let handle = span!(Level::DEBUG, "SpawningThread").in_scope(|| {
+      std::thread::spawn(move || {
+        let span = span!(Level::DEBUG, "BatchEventHandlingThread");
+        let _enter = span.enter();
+
+        // Running async function synchronously within another thread.
+        let rt = Runtime::new().unwrap();
+        rt.block_on(handle_batched_events(
+          input,
+          |batch: Vec<Event>| f(batch),
+          &retry_policy,
+          utils,
+          max_items,
+          max_latency,
+        ))
+        .or_else(|err| {
+          event!(Level::ERROR, label=%Events::EventHandlerFailure, ?err);
+          Err(err)
+        })
+        .expect("request loop failed");
+      })
+    });
+
+    Ok(handle)
+  }
+}
+
+// Accumulate events from the StageReceiver and flush them in batches.
+async fn handle_batched_events<
+  E: Debug + ErrorPolicyProvider + 'static,
+  R: Future<Output = Result<(), E>>,
+>(
+  input: StageReceiver,
+  callback_fn: impl Fn(Vec<Event>) -> R,
+  retry_policy: &RetryPolicy,
+  utils: Arc<Utils>,
+  max_items: usize,
+  max_latency: Duration,
+) -> Result<(), E> {
+  let span = span!(Level::INFO, "handle_batched_events");
+  let _enter = span.enter();
+
+  let mut buffer: Vec<Event> = Vec::with_capacity(max_items);
+  // Reset after every flush and whenever the buffer starts filling back up
+  // from empty, so `max_latency` bounds the age of the oldest *currently*
+  // buffered event rather than accumulating across flushes or idle gaps.
+  let mut deadline = Instant::now() + max_latency;
+
+  loop {
+    // StageReceiver is a blocking std::sync::mpsc channel, so we poll it with
+    // a short timeout instead of select!-ing on an async recv; the deadline
+    // check below still flushes on latency even if no event arrives before it.
+    match input.recv_timeout(Duration::from_millis(50)) {
+      Ok(chain_event) => {
+        deadline = next_deadline(buffer.is_empty(), Instant::now(), deadline, max_latency);
+        buffer.push(chain_event);
+      }
+      Err(RecvTimeoutError::Timeout) => {}
+      Err(RecvTimeoutError::Disconnected) => {
+        if !buffer.is_empty() {
+          flush(&mut buffer, &callback_fn, retry_policy, &utils).await?;
+        }
+        return Ok(());
+      }
+    }
+
+    if should_flush(buffer.len(), max_items, Instant::now(), deadline) {
+      flush(&mut buffer, &callback_fn, retry_policy, &utils).await?;
+      deadline = Instant::now() + max_latency;
+    }
+  }
+}
+
+// Whether the buffer has enough in it, or has been waiting long enough, to
+// flush right now.
+fn should_flush(buffer_len: usize, max_items: usize, now: Instant, deadline: Instant) -> bool {
+  buffer_len > 0 && (buffer_len >= max_items || now >= deadline)
+}
+
+// The deadline a buffer should have after pushing an event onto it: reset to
+// a fresh `max_latency` window if this event is the first in the buffer,
+// otherwise unchanged -- so an idle gap doesn't leave a stale deadline in the
+// past for whatever arrives next.
+fn next_deadline(
+  buffer_was_empty: bool,
+  now: Instant,
+  deadline: Instant,
+  max_latency: Duration,
+) -> Instant {
+  if buffer_was_empty {
+    now + max_latency
+  } else {
+    deadline
+  }
+}
+
+// Flush a full batch through the retry policy, then advance progress only as
+// far as the last event in the batch, so progress never races ahead of
+// durably-handled events.
+async fn flush<E: Debug + ErrorPolicyProvider + 'static, R: Future<Output = Result<(), E>>>(
+  buffer: &mut Vec<Event>,
+  callback_fn: &impl Fn(Vec<Event>) -> R,
+  retry_policy: &RetryPolicy,
+  utils: &Arc<Utils>,
+) -> Result<(), E> {
+  let batch = std::mem::take(buffer);
+  let span = span!(Level::INFO, "FlushingBatch", batch_size = batch.len());
+  let last_event = batch.last().cloned();
+
+  perform_with_retry(|| callback_fn(batch.clone()), retry_policy)
+    .instrument(span)
+    .await
+    .map(|_| {
+      if let Some(last_event) = &last_event {
+        utils.track_sink_progress(last_event);
+      }
+    })
+}
+
+#[derive(Display)]
+pub enum Events {
+  EventHandlerFailure,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_flush_is_false_for_an_empty_buffer_even_past_deadline() {
+    let now = Instant::now();
+    let past_deadline = now - Duration::from_secs(1);
+    assert!(!should_flush(0, 10, now, past_deadline));
+  }
+
+  #[test]
+  fn should_flush_on_reaching_max_items() {
+    let now = Instant::now();
+    let far_future_deadline = now + Duration::from_secs(60);
+    assert!(should_flush(10, 10, now, far_future_deadline));
+  }
+
+  #[test]
+  fn should_flush_once_deadline_has_passed() {
+    let now = Instant::now();
+    let past_deadline = now - Duration::from_millis(1);
+    assert!(should_flush(1, 10, now, past_deadline));
+  }
+
+  #[test]
+  fn should_not_flush_below_max_items_and_before_deadline() {
+    let now = Instant::now();
+    let future_deadline = now + Duration::from_secs(60);
+    assert!(!should_flush(1, 10, now, future_deadline));
+  }
+
+  #[test]
+  fn next_deadline_resets_when_buffer_was_empty_even_if_stale() {
+    let now = Instant::now();
+    let stale_deadline = now - Duration::from_secs(30); // lapsed during an idle gap
+    let max_latency = Duration::from_secs(5);
+
+    let deadline = next_deadline(true, now, stale_deadline, max_latency);
+
+    assert!(deadline >= now + max_latency);
+  }
+
+  #[test]
+  fn next_deadline_is_unchanged_when_buffer_was_not_empty() {
+    let now = Instant::now();
+    let deadline = now + Duration::from_secs(5);
+
+    assert_eq!(
+      next_deadline(false, now, deadline, Duration::from_secs(60)),
+      deadline
+    );
+  }
+}
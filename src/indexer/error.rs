@@ -0,0 +1,68 @@
+use thiserror::Error as ThisError;
+use tx_bakery::error::Recoverability;
+
+/// Governs how [`crate::indexer::retry::perform_with_retry`] reacts to a
+/// failed callback invocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ErrorPolicy {
+  /// Keep retrying, subject to the configured [`crate::indexer::retry::RetryPolicy`].
+  Continue,
+  /// Give up immediately and propagate the error.
+  Exit,
+}
+
+/// Implemented by the error type a sink's callback can fail with, so the
+/// retry loop can decide what to do without knowing the specifics of that
+/// error type.
+pub(crate) trait ErrorPolicyProvider {
+  fn error_policy(&self) -> ErrorPolicy;
+
+  /// If this error stems from a rejected transaction submission, classify it
+  /// as recoverable or not. Returns `None` for errors unrelated to submitting
+  /// a transaction (e.g. connection failures), which fall back to
+  /// `error_policy` alone.
+  fn is_invalid_transaction(&self) -> Option<Recoverability> {
+    None
+  }
+}
+
+/// Errors raised by indexer subsystems (e.g. [`crate::indexer::tracker::TxTracker`])
+/// that aren't tied to a single submitted transaction.
+#[derive(ThisError, Debug)]
+pub(crate) enum Error {
+  #[error("The upstream Oura pipeline closed before a terminal status was reached")]
+  UpstreamClosed,
+}
+
+impl ErrorPolicyProvider for Error {
+  fn error_policy(&self) -> ErrorPolicy {
+    ErrorPolicy::Exit
+  }
+}
+
+/// Bridges `tx_bakery`'s own `Error` into the indexer's retry machinery, so a
+/// `Callback`/`BatchingCallback` submitting transactions through `tx_bakery`
+/// gets the `Recoverable`/`Unrecoverable` short-circuit from
+/// [`tx_bakery::error::Error::is_invalid_transaction`] instead of silently
+/// falling back to this trait's `None` default.
+impl ErrorPolicyProvider for tx_bakery::error::Error {
+  fn error_policy(&self) -> ErrorPolicy {
+    match self {
+      // Chain/wallet communication and submission failures can clear up on
+      // their own (node catching up, mempool settling, transient I/O).
+      tx_bakery::error::Error::ChainQueryError(_)
+      | tx_bakery::error::Error::WalletError(_)
+      | tx_bakery::error::Error::SubmitterError(_) => ErrorPolicy::Continue,
+      // Everything else is a transaction-building/configuration error that
+      // retrying the same callback invocation can never fix.
+      _ => ErrorPolicy::Exit,
+    }
+  }
+
+  fn is_invalid_transaction(&self) -> Option<Recoverability> {
+    // Resolves to the inherent `tx_bakery::error::Error::is_invalid_transaction`
+    // (Rust prefers inherent methods over trait methods), not to this trait
+    // method's own default body.
+    self.is_invalid_transaction()
+  }
+}
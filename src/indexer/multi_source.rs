@@ -0,0 +1,227 @@
+use std::{
+  collections::HashSet,
+  sync::{Arc, Mutex},
+  time::{Duration, Instant},
+};
+
+use oura::{
+  model::{Event, EventData},
+  pipelining::StageReceiver,
+};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{event, span, Level};
+
+/// How long a source can go without emitting an event before it's reported as
+/// stalled. The source itself isn't dropped -- `MultiSource` keeps listening
+/// to it in case it recovers -- this is only used to report health.
+const DEFAULT_STALL_THRESHOLD: Duration = Duration::from_secs(60);
+
+/// Liveness of one of `MultiSource`'s upstream sources.
+#[derive(Debug, Clone)]
+pub(crate) struct SourceHealth {
+  pub(crate) source: usize,
+  pub(crate) last_event_at: Instant,
+  pub(crate) stall_threshold: Duration,
+}
+
+impl SourceHealth {
+  pub(crate) fn is_stalled(&self) -> bool {
+    self.last_event_at.elapsed() > self.stall_threshold
+  }
+}
+
+/// Bootstraps several upstream `StageReceiver`s (distinct relays/nodes) and
+/// merges them into a single deduplicated event stream, so a node that
+/// stalls or falls behind doesn't stall chain following -- whichever source
+/// delivers a given block first wins, and the same block arriving from
+/// another source afterwards is dropped.
+pub(crate) struct MultiSource {
+  sources: Vec<StageReceiver>,
+  stall_threshold: Duration,
+}
+
+impl MultiSource {
+  pub(crate) fn new(sources: Vec<StageReceiver>) -> Self {
+    MultiSource {
+      sources,
+      stall_threshold: DEFAULT_STALL_THRESHOLD,
+    }
+  }
+
+  pub(crate) fn with_stall_threshold(mut self, stall_threshold: Duration) -> Self {
+    self.stall_threshold = stall_threshold;
+    self
+  }
+
+  /// Merge all sources into a single deduplicated event stream, plus a
+  /// shared health table the caller can poll (via [`SourceHealth::is_stalled`])
+  /// to see which sources have gone quiet.
+  pub(crate) fn merge(self) -> (ReceiverStream<Event>, Arc<Mutex<Vec<SourceHealth>>>) {
+    let (tx, rx) = mpsc::channel(256);
+    let stall_threshold = self.stall_threshold;
+    let health = Arc::new(Mutex::new(
+      (0..self.sources.len())
+        .map(|source| SourceHealth {
+          source,
+          last_event_at: Instant::now(),
+          stall_threshold,
+        })
+        .collect::<Vec<_>>(),
+    ));
+    // (slot, block_hash) pairs already handed to a consumer, shared across
+    // every source's worker so the same block arriving twice is dropped
+    // regardless of which source delivers it second.
+    let seen: Arc<Mutex<HashSet<(u64, String)>>> = Arc::new(Mutex::new(HashSet::new()));
+
+    for (idx, input) in self.sources.into_iter().enumerate() {
+      let tx = tx.clone();
+      let health = Arc::clone(&health);
+      let seen = Arc::clone(&seen);
+
+      tokio::task::spawn_blocking(move || {
+        let span = span!(Level::DEBUG, "MultiSourceWorker", source = idx);
+        let _enter = span.enter();
+
+        for chain_event in input.iter() {
+          if let Some(entry) = health
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|h| h.source == idx)
+          {
+            entry.last_event_at = Instant::now();
+          }
+
+          if !admit(&chain_event, &seen) {
+            continue;
+          }
+
+          if tx.blocking_send(chain_event).is_err() {
+            event!(Level::WARN, label = "MultiSourceConsumerDropped", source = idx);
+            break;
+          }
+        }
+
+        event!(Level::WARN, label = "MultiSourceSourceExhausted", source = idx);
+      });
+    }
+
+    (ReceiverStream::new(rx), health)
+  }
+}
+
+/// Decide whether `chain_event` should be forwarded to the consumer: `false`
+/// if some source already delivered the same `(slot, block_hash)`. A
+/// rollback reported by any source rewinds the dedup window for slots at or
+/// after the rollback point, so a block re-included after the reorg isn't
+/// dropped as already-seen.
+///
+/// The rollback point comes from `EventData::RollBack { block_slot, .. }`
+/// itself rather than `chain_event.context`, which (as in `tracker.rs`) isn't
+/// reliably populated with a "current block" for a rewind marker.
+fn admit(chain_event: &Event, seen: &Arc<Mutex<HashSet<(u64, String)>>>) -> bool {
+  if let EventData::RollBack { block_slot, .. } = &chain_event.data {
+    seen.lock().unwrap().retain(|(slot, _)| *slot < *block_slot);
+    return true;
+  }
+
+  let Some(key) = dedup_key(chain_event) else {
+    return true; // events without block context always pass through
+  };
+
+  seen.lock().unwrap().insert(key)
+}
+
+fn dedup_key(event: &Event) -> Option<(u64, String)> {
+  let slot = event.context.slot?;
+  let block_hash = event.context.block_hash.clone()?;
+  Some((slot, block_hash))
+}
+
+#[cfg(test)]
+mod tests {
+  use oura::model::{BlockRecord, EventContext};
+
+  use super::*;
+
+  fn block_event(slot: u64, block_hash: &str) -> Event {
+    Event {
+      context: EventContext {
+        slot: Some(slot),
+        block_hash: Some(block_hash.to_string()),
+        ..Default::default()
+      },
+      data: EventData::Block(BlockRecord::default()),
+      fingerprint: None,
+    }
+  }
+
+  fn rollback_event(block_slot: u64) -> Event {
+    Event {
+      context: EventContext::default(),
+      data: EventData::RollBack {
+        block_slot,
+        block_hash: "deadbeef".to_string(),
+      },
+      fingerprint: None,
+    }
+  }
+
+  fn no_context_event() -> Event {
+    Event {
+      context: EventContext::default(),
+      data: EventData::Block(BlockRecord::default()),
+      fingerprint: None,
+    }
+  }
+
+  #[test]
+  fn dedup_key_requires_both_slot_and_block_hash() {
+    assert_eq!(dedup_key(&block_event(1, "a")), Some((1, "a".to_string())));
+    assert_eq!(dedup_key(&no_context_event()), None);
+  }
+
+  #[test]
+  fn admit_allows_the_first_source_to_deliver_a_block() {
+    let seen = Arc::new(Mutex::new(HashSet::new()));
+    assert!(admit(&block_event(1, "a"), &seen));
+  }
+
+  #[test]
+  fn admit_rejects_the_same_block_from_a_second_source() {
+    let seen = Arc::new(Mutex::new(HashSet::new()));
+    assert!(admit(&block_event(1, "a"), &seen));
+    assert!(!admit(&block_event(1, "a"), &seen));
+  }
+
+  #[test]
+  fn admit_always_forwards_rollback_events() {
+    let seen = Arc::new(Mutex::new(HashSet::new()));
+    assert!(admit(&rollback_event(1), &seen));
+  }
+
+  #[test]
+  fn admit_rewinds_the_dedup_window_on_rollback() {
+    let seen = Arc::new(Mutex::new(HashSet::new()));
+    assert!(admit(&block_event(1, "a"), &seen));
+    assert!(admit(&block_event(2, "b"), &seen));
+
+    // Reorg back to slot 1: everything at or after it should be forgotten.
+    admit(&rollback_event(1), &seen);
+
+    assert!(admit(&block_event(1, "a2"), &seen));
+    assert!(admit(&block_event(2, "b2"), &seen));
+  }
+
+  #[test]
+  fn admit_leaves_slots_before_the_rollback_point_deduplicated() {
+    let seen = Arc::new(Mutex::new(HashSet::new()));
+    assert!(admit(&block_event(1, "a"), &seen));
+
+    // Reorg back to slot 2: slot 1 was already final and should stay deduplicated.
+    admit(&rollback_event(2), &seen);
+
+    assert!(!admit(&block_event(1, "a"), &seen));
+  }
+}
@@ -0,0 +1,126 @@
+use std::{future::Future, time::Duration};
+
+use tracing::{event, Level};
+
+use super::error::{ErrorPolicy, ErrorPolicyProvider};
+
+/// Bounds on how long [`perform_with_retry`] keeps retrying a failing callback.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RetryPolicy {
+  pub(crate) max_retries: usize,
+  pub(crate) backoff: Duration,
+}
+
+/// Run `f`, retrying on failure according to `policy` and the error's own
+/// [`ErrorPolicyProvider`].
+///
+/// Before sleeping and retrying, we ask the error whether it stems from an
+/// invalid transaction submission. A `Recoverable` classification (e.g.
+/// fee-too-small, inputs not yet present, outdated validity interval) is
+/// retried as usual, but an `Unrecoverable` one (script failure, value not
+/// conserved, missing required signer) short-circuits immediately instead of
+/// burning the retry budget on something that can never succeed.
+pub(crate) async fn perform_with_retry<F, R, T, E>(f: F, policy: &RetryPolicy) -> Result<T, E>
+where
+  F: Fn() -> R,
+  R: Future<Output = Result<T, E>>,
+  E: ErrorPolicyProvider + std::fmt::Debug,
+{
+  let mut attempt = 0;
+  loop {
+    match f().await {
+      Ok(value) => return Ok(value),
+      Err(err) => {
+        if let Some(recoverability) = err.is_invalid_transaction() {
+          match recoverability {
+            tx_bakery::error::Recoverability::Unrecoverable(reason) => {
+              event!(Level::ERROR, label = "UnrecoverableSubmission", %reason);
+              return Err(err);
+            }
+            tx_bakery::error::Recoverability::Recoverable(reason) => {
+              event!(Level::WARN, label = "RecoverableSubmission", %reason);
+            }
+          }
+        }
+
+        match err.error_policy() {
+          ErrorPolicy::Exit => {
+            event!(Level::ERROR, label = "RetryAborted", ?err);
+            return Err(err);
+          }
+          ErrorPolicy::Continue => {
+            attempt += 1;
+            if attempt > policy.max_retries {
+              event!(Level::ERROR, label = "RetriesExhausted", ?err);
+              return Err(err);
+            }
+            event!(Level::WARN, label = "Retrying", attempt, ?err);
+            tokio::time::sleep(policy.backoff).await;
+          }
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  use tx_bakery::{error::Error as TxBakeryError, submitter::SubmitterError};
+
+  use super::*;
+
+  fn policy() -> RetryPolicy {
+    RetryPolicy {
+      max_retries: 3,
+      backoff: Duration::from_millis(0),
+    }
+  }
+
+  #[tokio::test]
+  async fn short_circuits_on_unrecoverable_submission_error() {
+    let calls = AtomicUsize::new(0);
+
+    let result = perform_with_retry(
+      || {
+        calls.fetch_add(1, Ordering::SeqCst);
+        async {
+          Err::<(), _>(TxBakeryError::SubmitterError(SubmitterError::TransactionRejected(
+            "PlutusFailure: script did not validate".to_string(),
+          )))
+        }
+      },
+      &policy(),
+    )
+    .await;
+
+    assert!(result.is_err());
+    // Only the first attempt should run -- an unrecoverable rejection must
+    // not burn the retry budget.
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+  }
+
+  #[tokio::test]
+  async fn keeps_retrying_recoverable_submission_error_until_budget_exhausted() {
+    let calls = AtomicUsize::new(0);
+    let retry_policy = policy();
+
+    let result = perform_with_retry(
+      || {
+        calls.fetch_add(1, Ordering::SeqCst);
+        async {
+          Err::<(), _>(TxBakeryError::SubmitterError(SubmitterError::TransactionRejected(
+            "FeeTooSmallUTxO: fee too small".to_string(),
+          )))
+        }
+      },
+      &retry_policy,
+    )
+    .await;
+
+    assert!(result.is_err());
+    // Initial attempt plus max_retries retries.
+    assert_eq!(calls.load(Ordering::SeqCst), retry_policy.max_retries + 1);
+  }
+}
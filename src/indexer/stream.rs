@@ -0,0 +1,96 @@
+use std::{fmt::Debug, future::Future, sync::Arc};
+
+use futures::{Stream, StreamExt};
+use oura::{model::Event, pipelining::StageReceiver, utils::Utils};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{event, span, Instrument, Level};
+
+use super::{
+  error::ErrorPolicyProvider,
+  retry::{perform_with_retry, RetryPolicy},
+};
+
+/// Channel buffer size between the blocking `StageReceiver` and the async stream.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Paired with the [`Stream`] returned by [`event_stream`]; acknowledges that
+/// a yielded [`Event`] has been durably processed.
+///
+/// Progress only advances once the consumer calls [`EventHandle::ack`], so an
+/// application that crashes mid-item still resumes at that item on restart
+/// (at-least-once delivery) instead of skipping it.
+#[derive(Clone)]
+pub(crate) struct EventHandle {
+  utils: Arc<Utils>,
+}
+
+impl EventHandle {
+  pub(crate) fn ack(&self, event: &Event) {
+    self.utils.track_sink_progress(event);
+  }
+}
+
+/// Expose the chain events from a `StageReceiver` as an async [`Stream`],
+/// instead of the `std::thread` plus its own `tokio::Runtime` that
+/// [`super::callback::Callback`] uses. This lets callers use `StreamExt`
+/// combinators, apply their own concurrency, and `select!` against other
+/// async work, integrating into an existing async application rather than
+/// hiding backpressure behind a detached thread.
+pub(crate) fn event_stream(
+  input: StageReceiver,
+  utils: Arc<Utils>,
+) -> (ReceiverStream<Event>, EventHandle) {
+  let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+
+  // `StageReceiver` is a blocking std::sync::mpsc channel, so we drain it
+  // into the async channel from a blocking task rather than Callback's
+  // detached std::thread::spawn plus its own Runtime.
+  tokio::task::spawn_blocking(move || {
+    let span = span!(Level::DEBUG, "EventStreamSourceTask");
+    let _enter = span.enter();
+    for chain_event in input.iter() {
+      if tx.blocking_send(chain_event).is_err() {
+        event!(Level::WARN, label = "StreamConsumerDropped");
+        break;
+      }
+    }
+  });
+
+  (ReceiverStream::new(rx), EventHandle { utils })
+}
+
+/// Stream adapter applying `retry_policy` to a fallible per-event handler, so
+/// the retry/error-policy behavior [`super::callback::Callback`] applies per
+/// event is still available when consuming events as a [`Stream`].
+///
+/// Only acks `handle` once `handler` has succeeded for an event, preserving
+/// at-least-once delivery semantics.
+pub(crate) fn with_retry<S, E, F, R>(
+  events: S,
+  handle: EventHandle,
+  handler: F,
+  retry_policy: RetryPolicy,
+) -> impl Stream<Item = Result<Event, E>>
+where
+  S: Stream<Item = Event>,
+  F: Fn(Event) -> R + Clone,
+  R: Future<Output = Result<(), E>>,
+  E: Debug + ErrorPolicyProvider + 'static,
+{
+  events.then(move |chain_event| {
+    let handler = handler.clone();
+    let handle = handle.clone();
+    let retry_policy = retry_policy;
+    async move {
+      let span = span!(Level::INFO, "with_retry");
+      perform_with_retry(|| handler(chain_event.clone()), &retry_policy)
+        .instrument(span)
+        .await
+        .map(|_| {
+          handle.ack(&chain_event);
+          chain_event
+        })
+    }
+  })
+}
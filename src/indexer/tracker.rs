@@ -0,0 +1,308 @@
+use oura::{
+  model::{Event, EventData},
+  pipelining::StageReceiver,
+};
+use tokio::sync::mpsc;
+use tracing::{event, span, Level};
+
+use super::error::Error;
+
+/// Depth, in blocks, at which an included transaction is considered confirmed.
+pub(crate) type Confirmations = u64;
+
+/// Lifecycle of a submitted transaction, as observed from the chain events
+/// Oura hands us for the source(s) it is following.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TrackedStatus {
+  /// Submitted, not yet seen in a block.
+  InMempool,
+  /// Seen in a block, but not yet buried to the requested confirmation depth.
+  InBlock { slot: u64, block_hash: String },
+  /// Buried by `depth` blocks, where `depth` is still below `finality_depth`.
+  Confirmed { depth: u64 },
+  /// Buried by at least `finality_depth` blocks; a rollback is no longer plausible.
+  Finalized,
+  /// Included in a block, but the Plutus scripts failed phase-2 validation,
+  /// so only collateral was consumed rather than the intended outputs.
+  PhaseTwoInvalid,
+  /// The block that contained this transaction was rolled back by a reorg.
+  RolledBack,
+}
+
+impl TrackedStatus {
+  fn is_terminal(&self, confirmations: Confirmations) -> bool {
+    match self {
+      TrackedStatus::Finalized | TrackedStatus::PhaseTwoInvalid | TrackedStatus::RolledBack => {
+        true
+      }
+      TrackedStatus::Confirmed { depth } => *depth >= confirmations,
+      TrackedStatus::InMempool | TrackedStatus::InBlock { .. } => false,
+    }
+  }
+}
+
+/// Tracks the block that included our transaction, plus how many `Block`
+/// events we've observed since (i.e. depth in blocks, not slots -- Cardano
+/// slots and blocks aren't 1:1, so counting elapsed slots would wildly
+/// mis-measure confirmation depth).
+struct Inclusion {
+  slot: u64,
+  depth: u64,
+}
+
+/// Watches the chain events from a single Oura source for a specific
+/// transaction hash, and reports how deep it is buried.
+///
+/// Consumes the same [`StageReceiver`] as [`super::callback::Callback`], but
+/// instead of invoking a user callback per event, it folds events into a
+/// [`TrackedStatus`] for one transaction of interest.
+pub(crate) struct TxTracker {
+  input: StageReceiver,
+  finality_depth: u64,
+}
+
+impl TxTracker {
+  pub(crate) fn new(input: StageReceiver, finality_depth: u64) -> Self {
+    TxTracker {
+      input,
+      finality_depth,
+    }
+  }
+
+  /// Drive the tracker to a terminal [`TrackedStatus`] for `tx_hash`,
+  /// requiring `confirmations` blocks of depth before reporting `Confirmed`.
+  ///
+  /// `finality_depth` implicitly caps `confirmations`: once a transaction is
+  /// buried by `finality_depth` blocks, `fold_event` reports `Finalized` rather
+  /// than `Confirmed`, so a `confirmations` greater than `finality_depth`
+  /// could never be observed. `confirmations` is clamped to `finality_depth`
+  /// to avoid silently ignoring a caller-requested depth that can never be
+  /// reached.
+  ///
+  /// `StageReceiver::iter()` blocks the calling thread until the next event
+  /// arrives, so the receive loop runs on a `spawn_blocking` worker and
+  /// status updates are relayed back over a channel -- otherwise awaiting
+  /// this future would block a tokio worker thread for the whole tracking
+  /// duration.
+  pub(crate) async fn track(
+    self,
+    tx_hash: String,
+    confirmations: Confirmations,
+  ) -> Result<TrackedStatus, Error> {
+    let span = span!(Level::INFO, "TxTracker::track", %tx_hash);
+    let _enter = span.enter();
+
+    let (status_tx, mut status_rx) = mpsc::unbounded_channel();
+    let input = self.input;
+    let finality_depth = self.finality_depth;
+    let confirmations = confirmations.min(finality_depth);
+
+    tokio::task::spawn_blocking(move || {
+      let span = span!(Level::DEBUG, "TxTrackerWorker", %tx_hash);
+      let _enter = span.enter();
+
+      let mut included: Option<Inclusion> = None;
+      for chain_event in input.iter() {
+        let Some(status) = fold_event(&chain_event, &tx_hash, &mut included, finality_depth)
+        else {
+          continue;
+        };
+        let is_terminal = status.is_terminal(confirmations);
+        if status_tx.send(status).is_err() || is_terminal {
+          return;
+        }
+      }
+    });
+
+    let mut last_status = TrackedStatus::InMempool;
+    while let Some(status) = status_rx.recv().await {
+      event!(Level::DEBUG, ?status);
+      last_status = status.clone();
+      if status.is_terminal(confirmations) {
+        return Ok(status);
+      }
+    }
+
+    event!(Level::WARN, label = "UpstreamClosed", ?last_status);
+    Err(Error::UpstreamClosed)
+  }
+}
+
+fn fold_event(
+  chain_event: &Event,
+  tx_hash: &str,
+  included: &mut Option<Inclusion>,
+  finality_depth: u64,
+) -> Option<TrackedStatus> {
+  match &chain_event.data {
+    EventData::Transaction(record) if record.hash == tx_hash => {
+      let slot = chain_event.context.slot.unwrap_or_default();
+      let block_hash = chain_event.context.block_hash.clone().unwrap_or_default();
+      *included = Some(Inclusion { slot, depth: 0 });
+      if record.valid_contract == Some(false) {
+        Some(TrackedStatus::PhaseTwoInvalid)
+      } else {
+        Some(TrackedStatus::InBlock { slot, block_hash })
+      }
+    }
+    EventData::RollBack { block_slot, .. } => {
+      if included.as_ref().is_some_and(|inc| inc.slot >= *block_slot) {
+        *included = None;
+        Some(TrackedStatus::RolledBack)
+      } else {
+        None
+      }
+    }
+    EventData::Block(_) => {
+      let inclusion = included.as_mut()?;
+      inclusion.depth += 1;
+      Some(if inclusion.depth >= finality_depth {
+        TrackedStatus::Finalized
+      } else {
+        TrackedStatus::Confirmed {
+          depth: inclusion.depth,
+        }
+      })
+    }
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use oura::model::{EventContext, TransactionRecord};
+
+  use super::*;
+
+  fn transaction_event(
+    hash: &str,
+    slot: u64,
+    block_hash: &str,
+    valid_contract: Option<bool>,
+  ) -> Event {
+    Event {
+      context: EventContext {
+        slot: Some(slot),
+        block_hash: Some(block_hash.to_string()),
+        ..Default::default()
+      },
+      data: EventData::Transaction(TransactionRecord {
+        hash: hash.to_string(),
+        valid_contract,
+        ..Default::default()
+      }),
+      fingerprint: None,
+    }
+  }
+
+  fn block_event(slot: u64) -> Event {
+    Event {
+      context: EventContext {
+        slot: Some(slot),
+        ..Default::default()
+      },
+      data: EventData::Block(Default::default()),
+      fingerprint: None,
+    }
+  }
+
+  fn rollback_event(block_slot: u64) -> Event {
+    Event {
+      context: EventContext::default(),
+      data: EventData::RollBack {
+        block_slot,
+        block_hash: "deadbeef".to_string(),
+      },
+      fingerprint: None,
+    }
+  }
+
+  #[test]
+  fn is_terminal_reports_confirmed_only_at_or_past_requested_depth() {
+    assert!(!TrackedStatus::Confirmed { depth: 2 }.is_terminal(3));
+    assert!(TrackedStatus::Confirmed { depth: 3 }.is_terminal(3));
+  }
+
+  #[test]
+  fn is_terminal_treats_inmempool_and_inblock_as_nonterminal() {
+    assert!(!TrackedStatus::InMempool.is_terminal(0));
+    assert!(!TrackedStatus::InBlock {
+      slot: 1,
+      block_hash: "a".into()
+    }
+    .is_terminal(0));
+  }
+
+  #[test]
+  fn fold_event_reports_inblock_on_matching_transaction() {
+    let mut included = None;
+    let event = transaction_event("abc123", 100, "blockhash1", None);
+    let status = fold_event(&event, "abc123", &mut included, 5);
+    assert_eq!(
+      status,
+      Some(TrackedStatus::InBlock {
+        slot: 100,
+        block_hash: "blockhash1".to_string()
+      })
+    );
+    assert!(included.is_some());
+  }
+
+  #[test]
+  fn fold_event_ignores_other_transactions() {
+    let mut included = None;
+    let event = transaction_event("other", 100, "blockhash1", None);
+    assert_eq!(fold_event(&event, "abc123", &mut included, 5), None);
+    assert!(included.is_none());
+  }
+
+  #[test]
+  fn fold_event_reports_phase_two_invalid() {
+    let mut included = None;
+    let event = transaction_event("abc123", 100, "blockhash1", Some(false));
+    assert_eq!(
+      fold_event(&event, "abc123", &mut included, 5),
+      Some(TrackedStatus::PhaseTwoInvalid)
+    );
+  }
+
+  #[test]
+  fn fold_event_counts_depth_by_block_events_not_slots() {
+    let mut included = Some(Inclusion { slot: 100, depth: 0 });
+    // A Block event 50 slots later should only add one block of depth, not 50.
+    let event = block_event(150);
+    assert_eq!(
+      fold_event(&event, "abc123", &mut included, 5),
+      Some(TrackedStatus::Confirmed { depth: 1 })
+    );
+  }
+
+  #[test]
+  fn fold_event_reports_finalized_past_finality_depth() {
+    let mut included = Some(Inclusion { slot: 100, depth: 4 });
+    let event = block_event(101);
+    assert_eq!(
+      fold_event(&event, "abc123", &mut included, 5),
+      Some(TrackedStatus::Finalized)
+    );
+  }
+
+  #[test]
+  fn fold_event_reports_rolled_back_when_rollback_covers_inclusion_slot() {
+    let mut included = Some(Inclusion { slot: 100, depth: 2 });
+    let event = rollback_event(100);
+    assert_eq!(
+      fold_event(&event, "abc123", &mut included, 5),
+      Some(TrackedStatus::RolledBack)
+    );
+    assert!(included.is_none());
+  }
+
+  #[test]
+  fn fold_event_ignores_rollback_before_inclusion_slot() {
+    let mut included = Some(Inclusion { slot: 100, depth: 2 });
+    let event = rollback_event(50);
+    assert_eq!(fold_event(&event, "abc123", &mut included, 5), None);
+    assert!(included.is_some());
+  }
+}
@@ -11,6 +11,18 @@ use crate::{
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Whether a submission failure can be expected to clear up if the same
+/// transaction is retried, or whether retrying is futile.
+///
+/// The inner `String` carries the underlying node rejection message, so
+/// callers that short-circuit on `Unrecoverable` can still surface the
+/// original reason to the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recoverability {
+    Recoverable(String),
+    Unrecoverable(String),
+}
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Unable to find redeemer for minting policy (hash: {0:?})")]
@@ -67,3 +79,15 @@ pub enum Error {
     #[error("A POSIX time value is invalid: {0}")]
     InvalidPOSIXTime(String),
 }
+
+impl Error {
+    /// Delegate to the wrapped [`SubmitterError`], if this is a submission
+    /// failure, so retry loops can tell a transaction that will never succeed
+    /// apart from one that's merely waiting on the mempool or node tip.
+    pub fn is_invalid_transaction(&self) -> Option<Recoverability> {
+        match self {
+            Error::SubmitterError(err) => err.is_invalid_transaction(),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use thiserror::Error;
+use tracing::warn;
+
+use crate::error::Recoverability;
+
+/// Errors that can occur while submitting a transaction to a Cardano node
+/// (directly, or through a backend such as Ogmios or a local submission API).
+#[derive(Error, Debug)]
+pub enum SubmitterError {
+    #[error("Transaction submission failed: {0}")]
+    SubmissionFailed(String),
+
+    #[error("Transaction was rejected by the node: {0}")]
+    TransactionRejected(String),
+
+    #[error("Unable to connect to the submission backend: {0}")]
+    ConnectionError(String),
+}
+
+impl SubmitterError {
+    /// Classify the underlying node rejection (if any) as recoverable or not, so
+    /// callers driving a retry loop know whether resubmitting could ever succeed.
+    ///
+    /// Matches on the Cardano ledger error tags that show up in a node's
+    /// submission rejection payload. Unknown tags default to `Recoverable`, so an
+    /// error we don't recognize yet fails open (keeps retrying) rather than
+    /// giving up on a transaction that might still make it in.
+    pub fn is_invalid_transaction(&self) -> Option<Recoverability> {
+        let SubmitterError::TransactionRejected(rejection) = self else {
+            return None;
+        };
+
+        // Errors that can clear on their own if we wait and resubmit.
+        const RECOVERABLE_TAGS: &[&str] = &[
+            "FeeTooSmallUTxO",
+            "InsufficientFundsUTxO",
+            "BadInputsUTxO", // inputs not yet visible to the node
+            "OutsideValidityIntervalUTxO",
+            "ExpiredUTxO",
+        ];
+
+        // Errors that can never clear no matter how many times we resubmit.
+        const UNRECOVERABLE_TAGS: &[&str] = &[
+            "PlutusFailure",
+            "ValueNotConservedUTxO",
+            "MissingRequiredSigners",
+            "MissingScriptWitnessesUTxO",
+            "ScriptWitnessNotValidatingUTxO",
+        ];
+
+        if UNRECOVERABLE_TAGS.iter().any(|tag| rejection.contains(tag)) {
+            return Some(Recoverability::Unrecoverable(rejection.clone()));
+        }
+
+        if !RECOVERABLE_TAGS.iter().any(|tag| rejection.contains(tag)) {
+            // Not one of the rejection tags we know about, so we fail open:
+            // still recoverable, but worth flagging since it means one of the
+            // two tag lists above is missing an entry.
+            warn!(rejection, "unrecognized ledger rejection tag, defaulting to recoverable");
+        }
+
+        Some(Recoverability::Recoverable(rejection.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unrecoverable_tag_short_circuits() {
+        let err = SubmitterError::TransactionRejected(
+            "PlutusFailure: script did not validate".to_string(),
+        );
+        assert!(matches!(
+            err.is_invalid_transaction(),
+            Some(Recoverability::Unrecoverable(_))
+        ));
+    }
+
+    #[test]
+    fn known_recoverable_tag_is_recoverable() {
+        let err = SubmitterError::TransactionRejected("FeeTooSmallUTxO: fee too small".to_string());
+        assert!(matches!(
+            err.is_invalid_transaction(),
+            Some(Recoverability::Recoverable(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_tag_fails_open_to_recoverable() {
+        let err = SubmitterError::TransactionRejected("SomeBrandNewLedgerRule".to_string());
+        assert!(matches!(
+            err.is_invalid_transaction(),
+            Some(Recoverability::Recoverable(_))
+        ));
+    }
+
+    #[test]
+    fn non_rejection_errors_are_not_classified() {
+        let err = SubmitterError::ConnectionError("timed out".to_string());
+        assert_eq!(err.is_invalid_transaction(), None);
+    }
+}